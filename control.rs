@@ -0,0 +1,84 @@
+// PID-based, time-proportioned watering controller.
+//
+// A bare moisture threshold switches the pump every cycle, which oscillates
+// and can over-water. This drives the relay from a PID loop instead: the
+// controller's output (0.0..=1.0) is the fraction of a fixed window the pump
+// should run, and a centrifugal pump gets switched in whole on/off cycles
+// rather than chattered at the control loop's own rate.
+
+use embassy_time::{Duration, Instant};
+
+/// Proportional/integral/derivative gains for the moisture loop.
+pub struct PidGains {
+    pub kp: f32,
+    pub ki: f32,
+    pub kd: f32,
+}
+
+/// Anti-windup bound on the accumulated integral term.
+const INTEGRAL_LIMIT: f32 = 50.0;
+
+/// Time-proportioning window the pump's on-time is spread over.
+const WINDOW: Duration = Duration::from_secs(30);
+
+/// Safety cutoff: force the relay off once it's been on this long within a
+/// single window, protecting against a stuck-dry sensor pumping forever.
+const MAX_ON_TIME: Duration = Duration::from_secs(20);
+
+/// Turns a soil-moisture error into a time-proportioned relay command.
+pub struct WateringController {
+    setpoint: f32,
+    gains: PidGains,
+    integral: f32,
+    prev_error: f32,
+    window_start: Instant,
+    on_time_this_window: Duration,
+}
+
+impl WateringController {
+    pub fn new(setpoint: f32, gains: PidGains, now: Instant) -> Self {
+        Self {
+            setpoint,
+            gains,
+            integral: 0.0,
+            prev_error: 0.0,
+            window_start: now,
+            on_time_this_window: Duration::from_ticks(0),
+        }
+    }
+
+    /// Feeds one new soil-moisture sample taken `dt` after the last one and
+    /// returns whether the relay should be energized right now.
+    pub fn update(&mut self, soil_moisture: f32, dt: Duration, now: Instant) -> bool {
+        if now - self.window_start >= WINDOW {
+            self.window_start = now;
+            self.on_time_this_window = Duration::from_ticks(0);
+        }
+
+        let dt_s = dt.as_micros() as f32 / 1_000_000.0;
+        let error = self.setpoint - soil_moisture;
+        self.integral = (self.integral + error * dt_s).clamp(-INTEGRAL_LIMIT, INTEGRAL_LIMIT);
+        let derivative = if dt_s > 0.0 {
+            (error - self.prev_error) / dt_s
+        } else {
+            0.0
+        };
+        self.prev_error = error;
+
+        let output = (self.gains.kp * error + self.gains.ki * self.integral + self.gains.kd * derivative)
+            .clamp(0.0, 1.0);
+
+        let on_budget_us = WINDOW.as_micros() as f32 * output;
+        let elapsed_us = (now - self.window_start).as_micros() as f32;
+        let within_budget = elapsed_us < on_budget_us;
+
+        let safety_tripped = self.on_time_this_window >= MAX_ON_TIME;
+        let relay_on = within_budget && !safety_tripped;
+
+        if relay_on {
+            self.on_time_this_window += dt;
+        }
+
+        relay_on
+    }
+}
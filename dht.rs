@@ -0,0 +1,107 @@
+// One-wire DHT22 humidity + temperature driver.
+//
+// The sensor is bit-banged over a single GPIO using `Flex` so the pin can
+// flip between output (to issue the start pulse) and input (to read back
+// the response) without a second wire.
+
+use embassy_rp::gpio::{Flex, Pull};
+use embassy_time::{Duration, Instant, Timer};
+
+/// Time the host holds the line low to wake the sensor up.
+const START_LOW: Duration = Duration::from_micros(18_000);
+
+/// Longest we'll wait for any single edge before giving up. A missing or
+/// disconnected sensor would otherwise leave the caller parked forever.
+const EDGE_TIMEOUT: Duration = Duration::from_micros(1_000);
+
+/// A high pulse longer than this decodes as a `1` bit, shorter as a `0` bit.
+const BIT_THRESHOLD_US: u64 = 50;
+
+/// A single relative-humidity / temperature reading.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Reading {
+    /// Relative humidity, in percent.
+    pub humidity: f32,
+    /// Temperature, in degrees Celsius.
+    pub temperature: f32,
+}
+
+/// Failure modes while talking to the sensor.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DhtError {
+    /// An expected edge never arrived; likely no sensor on the line.
+    Timeout,
+    /// The trailing checksum byte didn't match the four data bytes.
+    ChecksumMismatch,
+}
+
+/// Driver for a DHT22 sensor on a single GPIO. The data bytes are decoded
+/// in the DHT22's 16-bit tenths format only; a DHT11's whole-number bytes
+/// are not decoded correctly.
+pub struct Dht22<'d> {
+    pin: Flex<'d>,
+}
+
+impl<'d> Dht22<'d> {
+    pub fn new(pin: Flex<'d>) -> Self {
+        Self { pin }
+    }
+
+    /// Runs one full read cycle: start pulse, sensor response, 40 data bits.
+    pub async fn read(&mut self) -> Result<Reading, DhtError> {
+        self.pin.set_low();
+        self.pin.set_as_output();
+        Timer::after(START_LOW).await;
+
+        self.pin.set_as_input();
+        self.pin.set_pull(Pull::Up);
+
+        // Sensor's own start pulse: ~80us low, then ~80us high.
+        self.wait_for_level(false).await?;
+        self.wait_for_level(true).await?;
+        self.wait_for_level(false).await?;
+
+        let mut bytes = [0u8; 5];
+        for byte in bytes.iter_mut() {
+            for _ in 0..8 {
+                self.wait_for_level(true).await?;
+                let high_start = Instant::now();
+                self.wait_for_level(false).await?;
+                let high_us = high_start.elapsed().as_micros();
+                *byte = (*byte << 1) | (high_us > BIT_THRESHOLD_US) as u8;
+            }
+        }
+
+        let checksum = bytes[0]
+            .wrapping_add(bytes[1])
+            .wrapping_add(bytes[2])
+            .wrapping_add(bytes[3]);
+        if checksum != bytes[4] {
+            return Err(DhtError::ChecksumMismatch);
+        }
+
+        let humidity = u16::from_be_bytes([bytes[0], bytes[1]]) as f32 / 10.0;
+
+        let temp_raw = u16::from_be_bytes([bytes[2], bytes[3]]);
+        let negative = temp_raw & 0x8000 != 0;
+        let magnitude = (temp_raw & 0x7FFF) as f32 / 10.0;
+        let temperature = if negative { -magnitude } else { magnitude };
+
+        Ok(Reading {
+            humidity,
+            temperature,
+        })
+    }
+
+    /// Busy-waits until the line reaches `level`, bailing out with
+    /// [`DhtError::Timeout`] if it takes longer than `EDGE_TIMEOUT`.
+    async fn wait_for_level(&mut self, level: bool) -> Result<(), DhtError> {
+        let start = Instant::now();
+        while self.pin.is_high() != level {
+            if start.elapsed() > EDGE_TIMEOUT {
+                return Err(DhtError::Timeout);
+            }
+        }
+        Ok(())
+    }
+}
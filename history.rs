@@ -0,0 +1,163 @@
+// Scrolling history of soil moisture, temperature and light, rendered as a
+// small line chart in a corner of the display. A single instantaneous mood
+// reading can't show whether the pot has been trending dry or cold, so this
+// keeps the last few minutes of samples around and draws them incrementally.
+//
+// Samples live in a fixed-size ring buffer with a stable column per slot
+// (an oscilloscope-style sweep, rather than shifting every point left each
+// frame), so filling the buffer and wrapping around never costs more than
+// repainting the couple of segments touching the slot that just changed.
+
+use embedded_graphics::draw_target::DrawTarget;
+use embedded_graphics::geometry::Point;
+use embedded_graphics::pixelcolor::Rgb565;
+use embedded_graphics::prelude::*;
+use embedded_graphics::primitives::{Line, PrimitiveStyle};
+use heapless::Vec;
+
+/// Samples kept per metric; once full, the oldest slot is overwritten.
+pub const HISTORY_LEN: usize = 120;
+
+/// Top-left origin of the chart panel, in display pixels.
+const CHART_ORIGIN: Point = Point::new(4, 4);
+const CHART_WIDTH: u32 = 120;
+const CHART_HEIGHT: u32 = 40;
+
+/// One metric's ring-buffered samples, plotted over a fixed `min..=max`.
+struct Series {
+    samples: Vec<f32, HISTORY_LEN>,
+    /// Slot the next sample will be written to.
+    write_at: usize,
+    /// Slot + its pre-overwrite value, pending the erase/redraw of the two
+    /// segments that touch it. `None` while still filling the buffer for
+    /// the first time, since nothing has been overwritten yet.
+    overwritten: Option<(usize, f32)>,
+    min: f32,
+    max: f32,
+    color: Rgb565,
+}
+
+impl Series {
+    fn new(min: f32, max: f32, color: Rgb565) -> Self {
+        Self {
+            samples: Vec::new(),
+            write_at: 0,
+            overwritten: None,
+            min,
+            max,
+            color,
+        }
+    }
+
+    fn push(&mut self, value: f32) {
+        if self.samples.len() < HISTORY_LEN {
+            let _ = self.samples.push(value);
+            self.write_at = self.samples.len() % HISTORY_LEN;
+        } else {
+            let old_value = self.samples[self.write_at];
+            self.samples[self.write_at] = value;
+            self.overwritten = Some((self.write_at, old_value));
+            self.write_at = (self.write_at + 1) % HISTORY_LEN;
+        }
+    }
+
+    fn x(&self, index: usize) -> i32 {
+        CHART_ORIGIN.x + (index as u32 * CHART_WIDTH / HISTORY_LEN as u32) as i32
+    }
+
+    fn y(&self, value: f32) -> i32 {
+        let span = (self.max - self.min).max(f32::EPSILON);
+        let frac = (value.clamp(self.min, self.max) - self.min) / span;
+        CHART_ORIGIN.y + CHART_HEIGHT as i32 - 1 - (frac * (CHART_HEIGHT - 1) as f32) as i32
+    }
+
+    fn segment<D>(
+        &self,
+        target: &mut D,
+        from_idx: usize,
+        to_idx: usize,
+        from_val: f32,
+        to_val: f32,
+        color: Rgb565,
+    ) -> Result<(), D::Error>
+    where
+        D: DrawTarget<Color = Rgb565>,
+    {
+        let style = PrimitiveStyle::with_stroke(color, 1);
+        let from = Point::new(self.x(from_idx), self.y(from_val));
+        let to = Point::new(self.x(to_idx), self.y(to_val));
+        Line::new(from, to).into_styled(style).draw(target)
+    }
+
+    /// Paints only whatever changed since the last frame: either the newest
+    /// segment while still filling up, or the two segments touching the
+    /// slot that was just overwritten once the buffer has wrapped.
+    fn draw<D>(&mut self, target: &mut D) -> Result<(), D::Error>
+    where
+        D: DrawTarget<Color = Rgb565>,
+    {
+        let len = self.samples.len();
+        if len < 2 {
+            return Ok(());
+        }
+
+        if let Some((idx, old_value)) = self.overwritten.take() {
+            let prev = (idx + len - 1) % len;
+            let next = (idx + 1) % len;
+            // Erase the two old segments before redrawing with the new value,
+            // since the slot's column is reused rather than shifted.
+            self.segment(target, prev, idx, self.samples[prev], old_value, Rgb565::BLACK)?;
+            self.segment(target, idx, next, old_value, self.samples[next], Rgb565::BLACK)?;
+            self.segment(target, prev, idx, self.samples[prev], self.samples[idx], self.color)?;
+            self.segment(target, idx, next, self.samples[idx], self.samples[next], self.color)?;
+        } else {
+            let newest = if self.write_at == 0 { len - 1 } else { self.write_at - 1 };
+            if newest > 0 {
+                self.segment(
+                    target,
+                    newest - 1,
+                    newest,
+                    self.samples[newest - 1],
+                    self.samples[newest],
+                    self.color,
+                )?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Scrolling history of the three headline metrics.
+pub struct History {
+    soil: Series,
+    temperature: Series,
+    light: Series,
+}
+
+impl History {
+    pub fn new() -> Self {
+        Self {
+            soil: Series::new(0.0, 100.0, Rgb565::CSS_DODGER_BLUE),
+            temperature: Series::new(-10.0, 50.0, Rgb565::CSS_ORANGE_RED),
+            light: Series::new(0.0, 4000.0, Rgb565::YELLOW),
+        }
+    }
+
+    /// Records one sample of each metric for this loop iteration.
+    pub fn record(&mut self, soil_moisture: f32, temperature: f32, light: f32) {
+        self.soil.push(soil_moisture);
+        self.temperature.push(temperature);
+        self.light.push(light);
+    }
+
+    /// Paints whatever columns changed since the previous frame.
+    pub fn draw<D>(&mut self, target: &mut D) -> Result<(), D::Error>
+    where
+        D: DrawTarget<Color = Rgb565>,
+    {
+        self.soil.draw(target)?;
+        self.temperature.draw(target)?;
+        self.light.draw(target)?;
+        Ok(())
+    }
+}
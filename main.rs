@@ -17,7 +17,7 @@ use embassy_rp::spi::{Blocking, Spi};
 use embassy_rp::usb::{Driver, InterruptHandler as USBInterruptHandler};
 use embassy_sync::blocking_mutex::raw::NoopRawMutex;
 use embassy_sync::blocking_mutex::Mutex;
-use embassy_time::{Delay, Timer};
+use embassy_time::{Delay, Duration, Instant, Timer};
 use embedded_graphics::draw_target::DrawTarget;
 use embedded_graphics::geometry::Point;
 use embedded_graphics::image::{Image, ImageRawLE};
@@ -33,11 +33,24 @@ use log::info;
 use st7789::{Orientation, ST7789};
 
 mod display;
+mod dht;
+mod history;
+mod control;
+mod telemetry;
+mod statusled;
 
 use display::SPIDeviceInterface;
+use dht::Dht22;
+use history::History;
+use control::{PidGains, WateringController};
+use telemetry::TelemetryPort;
+use embassy_rp::uart::{Config as UartConfig, Uart};
+use statusled::{pump_pulse_color, Mood, StatusLed};
+use embassy_rp::peripherals::PIO0;
+use embassy_rp::pio::{InterruptHandler as PioInterruptHandler, Pio};
 
 use embassy_rp::adc::{Adc, Channel, Config as AdcConfig, InterruptHandler as AdcInterruptHandler};
-use embassy_rp::gpio::Pull;
+use embassy_rp::gpio::{Flex, Pull};
 
 
 const DISPLAY_FREQ: u32 = 64_000_000;
@@ -47,8 +60,30 @@ bind_interrupts!(struct Irqs {
     // Use for the serial over USB driver
     USBCTRL_IRQ => USBInterruptHandler<USB>;
     ADC_IRQ_FIFO => AdcInterruptHandler;
+    PIO0_IRQ_0 => PioInterruptHandler<PIO0>;
 });
 
+/// Number of WS2812 LEDs in the mood indicator chain.
+const STATUS_LED_COUNT: usize = 8;
+
+/// Which GPIO level energizes the pump relay. Most 3.3V-logic relay
+/// boards used with these kits (a single-channel opto-isolated module) are
+/// active-low — pulling the input LOW turns the relay on — so that's the
+/// default here. If your board is active-high instead, flip this one
+/// constant; nothing else needs to change.
+const RELAY_ACTIVE_LOW: bool = true;
+
+/// Maps "should the pump run" to the GPIO level that actually energizes it,
+/// per [`RELAY_ACTIVE_LOW`].
+fn relay_level(energize: bool) -> Level {
+    match (energize, RELAY_ACTIVE_LOW) {
+        (true, true) => Level::Low,
+        (true, false) => Level::High,
+        (false, true) => Level::High,
+        (false, false) => Level::Low,
+    }
+}
+
 #[embassy_executor::task]
 async fn logger_task(driver: Driver<'static, USB>) {
     embassy_usb_logger::run!(1024, log::LevelFilter::Info, driver);
@@ -98,7 +133,23 @@ async fn main(spawner: Spawner) {
     let mut temperature_sensor = Channel::new_pin(peripherals.PIN_27, Pull::None);
     let mut soil_sensor = Channel::new_pin(peripherals.PIN_26, Pull::None);
     let mut light_sensor = Channel::new_pin(peripherals.PIN_28, Pull::None);
-    let mut relay = Output::new(peripherals.PIN_0, Level::Low);
+    let mut relay = Output::new(peripherals.PIN_0, relay_level(false));
+    let mut dht = Dht22::new(Flex::new(peripherals.PIN_1));
+
+    // Dedicated UART for telemetry, separate from the USB debug channel.
+    let telemetry_uart = Uart::new_blocking(
+        peripherals.UART1,
+        peripherals.PIN_4,
+        peripherals.PIN_5,
+        UartConfig::default(),
+    );
+    let mut telemetry = TelemetryPort::new(telemetry_uart, Duration::from_secs(5));
+
+    // WS2812 mood LEDs, driven through PIO0's first state machine.
+    let Pio {
+        mut common, sm0, ..
+    } = Pio::new(peripherals.PIO0, Irqs);
+    let mut status_led = StatusLed::new(&mut common, sm0, peripherals.PIN_2);
 
 
     let mut display_config = spi::Config::default();
@@ -147,6 +198,8 @@ async fn main(spawner: Spawner) {
     let frozen = embedded_graphics::text::Text::new("Frozen castle", Point::new(130, 60), style);
     let hot = embedded_graphics::text::Text::new("Too hot", Point::new(130, 60), style);
     let fine = embedded_graphics::text::Text::new("FloraMood", Point::new(150, 20), style);
+    let parched_air = embedded_graphics::text::Text::new("Dry air", Point::new(130, 100), style);
+    let muggy = embedded_graphics::text::Text::new("Feeling muggy", Point::new(130, 100), style);
 
     let raw_image_data = ImageRawLE::new(include_bytes!("../assets/happy/image_1.raw"), 120);
     let mut ferris = Image::new(&raw_image_data, Point::new(150, 150));
@@ -216,7 +269,17 @@ async fn main(spawner: Spawner) {
     images.push(ImageRawLE::new(include_bytes!("../assets/happy/image_60.raw"), IMAGE_WIDTH)).unwrap();
     
     let mut i = 0;
-    let mut value = 1;
+    let mut history = History::new();
+    let mut last_loop = Instant::now();
+    let mut watering = WateringController::new(
+        20.0,
+        PidGains {
+            kp: 0.05,
+            ki: 0.01,
+            kd: 0.01,
+        },
+        last_loop,
+    );
 
     //////////////////////////////////////////////////////////////////////////////
 
@@ -231,8 +294,17 @@ async fn main(spawner: Spawner) {
         if(i%60==0){i=i/60;}
 
 
+        // DHT22 gives us real temperature and humidity; fall back to the
+        // analog estimate if a read fails so a flaky sensor doesn't stall
+        // the rest of the loop.
         let level = adc.read(&mut temperature_sensor).await.unwrap();
-        let temperature_value = adc_to_voltage(level) + 7.0;
+        let (temperature_value, humidity_value) = match dht.read().await {
+            Ok(reading) => (reading.temperature, Some(reading.humidity)),
+            Err(err) => {
+                info!("DHT22 read failed: {:?}", err);
+                (adc_to_voltage(level) + 7.0, None)
+            }
+        };
         info!("Temperature sensor reading: {:.2} Â°C", temperature_value);
         let level_soil = adc.read(&mut soil_sensor).await.unwrap();
         let level_light = adc.read(&mut light_sensor).await.unwrap();
@@ -249,39 +321,80 @@ async fn main(spawner: Spawner) {
         }
        
         
+        // Each metric's text block also feeds the shared alarm count below,
+        // rather than a separate pass re-deriving the same thresholds, so
+        // the display text and the mood/LED decision can't drift apart.
+        let mut alarms: u8 = 0;
+
         if soil_moisture < 10.0 {
             dry.draw(&mut display).unwrap();
             Timer::after_millis(100).await;
-            value = 0;
+            alarms += 1;
         }
         if soil_moisture > 10.0 {
             wet.draw(&mut display).unwrap();
             Timer::after_millis(100).await;
-            value = 1;
         }
-        match value {
-            0 => relay.set_low(), 
-            _ => relay.set_high(),
+        if soil_moisture > 70.0 {
+            // Genuinely overwatered, as opposed to merely "not dry" above —
+            // only this should count against the mood, or the LED would sit
+            // on Warning/Critical for most of normal operation.
+            alarms += 1;
         }
+
+        let now = Instant::now();
+        let dt = now - last_loop;
+        last_loop = now;
+        let relay_on = watering.update(soil_moisture, dt, now);
+        relay.set_level(relay_level(relay_on));
+        telemetry.emit(soil_moisture, temperature_value, light_value, relay_on, now);
         if light_value < 500.0 {
             dark.draw(&mut display).unwrap();
             Timer::after_millis(100).await;
+            alarms += 1;
             //relay.set_low();
         }
         if light_value > 2000.0 {
             diamond.draw(&mut display).unwrap();
             Timer::after_millis(100).await;
+            alarms += 1;
             //relay.set_high();
         }
         if temperature_value < 0.0 {
             frozen.draw(&mut display).unwrap();
             Timer::after_millis(100).await;
+            alarms += 1;
         }
         if temperature_value > 40.0 {
             hot.draw(&mut display).unwrap();
             Timer::after_millis(100).await;
+            alarms += 1;
         }
-        
+
+        if let Some(humidity) = humidity_value {
+            if humidity < 30.0 {
+                parched_air.draw(&mut display).unwrap();
+                Timer::after_millis(100).await;
+                alarms += 1;
+            }
+            if humidity > 70.0 {
+                muggy.draw(&mut display).unwrap();
+                Timer::after_millis(100).await;
+                alarms += 1;
+            }
+            info!("Humidity: {:.2}%", humidity);
+        }
+
+        let mood = Mood::from_alarm_count(alarms);
+        let led_color = if relay_on {
+            pump_pulse_color(i as u32)
+        } else {
+            mood.color()
+        };
+        status_led.write(&[led_color; STATUS_LED_COUNT]).await;
+
+        history.record(soil_moisture, temperature_value, light_value);
+        history.draw(&mut display).unwrap();
 
         // Log the soil moisture percentage
         info!("Soil Moisture: {:.2}%", soil_moisture);
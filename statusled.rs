@@ -0,0 +1,126 @@
+// WS2812 ("NeoPixel") mood LED chain, driven through the RP2040's PIO so the
+// precise one-wire timing doesn't have to be bit-banged on the CPU.
+//
+// Also home to the `Mood` enum, which turns the number of out-of-range
+// metrics into one overall plant mood and picks the LED color from it. The
+// display text is not chosen by the enum: each metric's block in `main`
+// draws its own message and adds to the alarm count the mood is built from.
+
+use embassy_rp::clocks::clk_sys_freq;
+use embassy_rp::peripherals::PIO0;
+use embassy_rp::pio::{Common, Config, FifoJoin, Instance, PioPin, ShiftDirection, StateMachine};
+use fixed::types::U24F8;
+use smart_leds::RGB8;
+
+/// One bit takes ~1.25us, split into three PIO-cycle phases (cf. the
+/// pico-examples `ws2812.pio`): always low for `T3`, always high for `T1`,
+/// then high for a further `T2` cycles only if the bit was a `1`. That
+/// gives a long-high/short-low pulse for `1` and a short-high/long-low
+/// pulse for `0`, with `T1 + T2 + T3` cycles making up the full bit period.
+const T1: u8 = 2;
+const T2: u8 = 5;
+const T3: u8 = 3;
+const CYCLE_TIME_NS: u32 = 1_250 / (T1 as u32 + T2 as u32 + T3 as u32);
+
+/// Overall plant mood, derived from how many metrics are currently out of
+/// their healthy range. Selects the color the status LEDs show; it does not
+/// select which message the display shows.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Mood {
+    /// Every metric is in range.
+    Good,
+    /// Exactly one metric has drifted out of range.
+    Warning,
+    /// Two or more metrics are out of range at once.
+    Critical,
+}
+
+impl Mood {
+    pub fn from_alarm_count(alarms: u8) -> Self {
+        match alarms {
+            0 => Mood::Good,
+            1 => Mood::Warning,
+            _ => Mood::Critical,
+        }
+    }
+
+    /// Base color for this mood; overridden by a pulsing blue while the
+    /// pump relay is active (see [`pump_pulse_color`]).
+    pub fn color(self) -> RGB8 {
+        match self {
+            Mood::Good => RGB8::new(0, 20, 0),
+            Mood::Warning => RGB8::new(20, 12, 0),
+            Mood::Critical => RGB8::new(20, 0, 0),
+        }
+    }
+}
+
+/// Blue, breathing between dim and bright, for as long as the pump is
+/// running. `tick` is a loop counter; the breathing repeats every 40 ticks.
+pub fn pump_pulse_color(tick: u32) -> RGB8 {
+    let phase = (tick % 40) as i32;
+    let level = if phase < 20 { phase } else { 40 - phase };
+    RGB8::new(0, 0, (level * 2) as u8)
+}
+
+/// Drives a chain of WS2812 LEDs over one PIO state machine.
+pub struct StatusLed<'d, P: Instance, const SM: usize> {
+    sm: StateMachine<'d, P, SM>,
+}
+
+impl<'d, P: Instance, const SM: usize> StatusLed<'d, P, SM> {
+    /// Loads the WS2812 PIO program and binds it to `pin`.
+    pub fn new(pio: &mut Common<'d, P>, mut sm: StateMachine<'d, P, SM>, pin: impl PioPin) -> Self {
+        let side_set = pio::SideSet::new(false, 1, false);
+        let mut a = pio::Assembler::<32>::new_with_side_set(side_set);
+
+        // bitloop: out x,1 side 0 [T3-1]     (always low for T3 cycles)
+        //          jmp !x do_zero side 1 [T1-1] (always high for T1 cycles)
+        // do_one:  jmp bitloop side 1 [T2-1]  (stay high another T2 -> long pulse)
+        // do_zero: nop side 0 [T2-1]          (go low instead -> short pulse)
+        let mut wrap_target = a.label();
+        let mut wrap_source = a.label();
+        let mut do_zero = a.label();
+        a.bind(&mut wrap_target);
+        a.out_with_delay_and_side_set(pio::OutDestination::X, 1, T3 - 1, 0);
+        a.jmp_with_delay_and_side_set(pio::JmpCondition::XIsZero, &mut do_zero, T1 - 1, 1);
+        a.jmp_with_delay_and_side_set(pio::JmpCondition::Always, &mut wrap_target, T2 - 1, 1);
+        a.bind(&mut do_zero);
+        a.nop_with_delay_and_side_set(T2 - 1, 0);
+        a.bind(&mut wrap_source);
+        let program = a.assemble_with_wrap(wrap_source, wrap_target);
+
+        let loaded = pio.load_program(&program);
+        let pin = pio.make_pio_pin(pin);
+        sm.set_pins(embassy_rp::gpio::Level::Low, &[&pin]);
+        sm.set_pin_dirs(embassy_rp::pio::Direction::Out, &[&pin]);
+
+        let mut cfg = Config::default();
+        cfg.use_program(&loaded, &[&pin]);
+        cfg.shift_out.direction = ShiftDirection::Left;
+        // Each color is 24 bits; autopull refills the OSR from the TX FIFO
+        // as soon as those 24 bits are shifted out, so `out x, 1` always
+        // has fresh data instead of spinning on a stale/zero OSR.
+        cfg.shift_out.auto_fill = true;
+        cfg.shift_out.threshold = 24;
+        cfg.fifo_join = FifoJoin::TxOnly;
+        cfg.clock_divider = clock_divider_for_ws2812();
+        sm.set_config(&cfg);
+        sm.set_enable(true);
+
+        Self { sm }
+    }
+
+    /// Pushes one GRB-ordered color per LED down the chain.
+    pub async fn write(&mut self, colors: &[RGB8]) {
+        for color in colors {
+            let word = ((color.g as u32) << 24) | ((color.r as u32) << 16) | ((color.b as u32) << 8);
+            self.sm.tx().wait_push(word).await;
+        }
+    }
+}
+
+/// Clock divider so one PIO cycle is `CYCLE_TIME_NS` long.
+fn clock_divider_for_ws2812() -> U24F8 {
+    U24F8::from_num(clk_sys_freq()) / U24F8::from_num(1_000_000_000 / CYCLE_TIME_NS)
+}
@@ -0,0 +1,100 @@
+// Structured UART telemetry for remote greenhouse monitoring.
+//
+// A single pot is easy to read over USB, but a grower with several pots
+// around a greenhouse wants one coordinator (e.g. an XBee/serial radio on a
+// PC) to collect all of them. This frames the current readings into a
+// compact, self-delimiting record and writes it out a dedicated UART, kept
+// separate from the USB debug channel.
+
+use embassy_rp::uart::{Blocking, Instance, Uart};
+use embassy_time::{Duration, Instant};
+
+/// Marks the start of a frame so a host can resync after noise on a long
+/// cable run.
+const START_BYTE: u8 = 0xAA;
+
+/// start byte + sequence (u16) + 3 little-endian f32 fields + relay byte + CRC-8.
+const FRAME_LEN: usize = 1 + 2 + 4 + 4 + 4 + 1 + 1;
+
+/// One telemetry record.
+struct Frame {
+    sequence: u16,
+    soil_moisture: f32,
+    temperature: f32,
+    light: f32,
+    relay_on: bool,
+}
+
+impl Frame {
+    fn encode(&self) -> [u8; FRAME_LEN] {
+        let mut buf = [0u8; FRAME_LEN];
+        buf[0] = START_BYTE;
+        buf[1..3].copy_from_slice(&self.sequence.to_le_bytes());
+        buf[3..7].copy_from_slice(&self.soil_moisture.to_le_bytes());
+        buf[7..11].copy_from_slice(&self.temperature.to_le_bytes());
+        buf[11..15].copy_from_slice(&self.light.to_le_bytes());
+        buf[15] = self.relay_on as u8;
+        buf[16] = crc8(&buf[1..16]);
+        buf
+    }
+}
+
+/// Polynomial 0x07 (CRC-8/SMBUS), computed bitwise since there's no table
+/// lookup machinery lying around for a 17-byte frame.
+fn crc8(data: &[u8]) -> u8 {
+    let mut crc: u8 = 0;
+    for &byte in data {
+        crc ^= byte;
+        for _ in 0..8 {
+            crc = if crc & 0x80 != 0 { (crc << 1) ^ 0x07 } else { crc << 1 };
+        }
+    }
+    crc
+}
+
+/// Periodically emits telemetry frames over a dedicated UART.
+pub struct TelemetryPort<'d, UART: Instance> {
+    uart: Uart<'d, UART, Blocking>,
+    interval: Duration,
+    last_sent: Option<Instant>,
+    sequence: u16,
+}
+
+impl<'d, UART: Instance> TelemetryPort<'d, UART> {
+    pub fn new(uart: Uart<'d, UART, Blocking>, interval: Duration) -> Self {
+        Self {
+            uart,
+            interval,
+            last_sent: None,
+            sequence: 0,
+        }
+    }
+
+    /// Sends the current readings if `interval` has elapsed since the last
+    /// frame, so a low-power radio isn't flooded every control-loop tick.
+    pub fn emit(
+        &mut self,
+        soil_moisture: f32,
+        temperature: f32,
+        light: f32,
+        relay_on: bool,
+        now: Instant,
+    ) {
+        if let Some(last) = self.last_sent {
+            if now - last < self.interval {
+                return;
+            }
+        }
+
+        let frame = Frame {
+            sequence: self.sequence,
+            soil_moisture,
+            temperature,
+            light,
+            relay_on,
+        };
+        self.sequence = self.sequence.wrapping_add(1);
+        let _ = self.uart.blocking_write(&frame.encode());
+        self.last_sent = Some(now);
+    }
+}